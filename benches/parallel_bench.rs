@@ -0,0 +1,64 @@
+//! Serial versus `parallel` timings for the evaluators behind
+//! [`PARALLEL_THRESHOLD`](xraydb_rs::interp::PARALLEL_THRESHOLD).
+//!
+//! Run once per mode and compare the tables:
+//!
+//! ```text
+//! cargo bench --bench parallel_bench
+//! cargo bench --bench parallel_bench --features parallel
+//! ```
+//!
+//! The threshold should sit where the parallel column stops losing to the
+//! serial one on a typical multi-core machine.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use xraydb_rs::interp::{LogTable, PARALLEL_THRESHOLD};
+use xraydb_rs::spline::{cubic_spline_coefficients, elam_spline, BoundaryCondition};
+
+const SIZES: [usize; 5] = [1024, PARALLEL_THRESHOLD, 16384, 65536, 100_000];
+
+/// Best time per call over enough repetitions to fill about 200 ms.
+fn time<F: FnMut() -> Vec<f64>>(mut f: F) -> Duration {
+    let start = Instant::now();
+    let mut best = Duration::MAX;
+    while start.elapsed() < Duration::from_millis(200) {
+        let t = Instant::now();
+        black_box(f());
+        best = best.min(t.elapsed());
+    }
+    best
+}
+
+fn main() {
+    // A table the size of a tabulated Elam cross section, 100 eV to 800 keV.
+    let knots: Vec<f64> = (0..300)
+        .map(|i| 100.0 * 8000f64.powf(i as f64 / 299.0))
+        .collect();
+    let values: Vec<f64> = knots.iter().map(|e| 1e9 / e.powf(2.7)).collect();
+    let log_x: Vec<f64> = knots.iter().map(|e| e.ln()).collect();
+    let log_y: Vec<f64> = values.iter().map(|v| v.ln()).collect();
+    let y2 = cubic_spline_coefficients(&log_x, &log_y, BoundaryCondition::Natural);
+    let table = LogTable::new(&knots, &values);
+
+    let mode = if cfg!(feature = "parallel") {
+        "parallel"
+    } else {
+        "serial"
+    };
+    println!("{mode} (threshold {PARALLEL_THRESHOLD})");
+    println!(
+        "{:>8} {:>14} {:>14}",
+        "points", "elam_spline", "LogTable::eval"
+    );
+    for n in SIZES {
+        let energies: Vec<f64> = (0..n)
+            .map(|i| 150.0 * 5000f64.powf(i as f64 / n as f64))
+            .collect();
+        let log_e: Vec<f64> = energies.iter().map(|e| e.ln()).collect();
+        let spline = time(|| elam_spline(&log_x, &log_y, &y2, black_box(&log_e)));
+        let loglog = time(|| table.eval(black_box(&energies)));
+        println!("{n:>8} {spline:>14.2?} {loglog:>14.2?}");
+    }
+}
@@ -0,0 +1,10 @@
+//! Physical constants (CODATA 2018).
+
+/// Planck constant times the speed of light, in eV·Å.
+///
+/// Exact in the 2019 SI: `E [eV] = PLANCK_HC_EV_ANGSTROM / λ [Å]`.
+pub const PLANCK_HC_EV_ANGSTROM: f64 = 12398.419843320026;
+
+/// ħ²/(2mₑ) in eV·Å², relating photoelectron kinetic energy to wavenumber:
+/// `E - E0 = KTOE * k²`.
+pub const KTOE: f64 = 3.80998211615486;
@@ -0,0 +1,75 @@
+//! Conversions between photon energy, wavelength, wavenumber and Bragg
+//! angle.
+//!
+//! Energies are in eV, lengths in Å and angles in radians. Everything is
+//! derived from [`crate::constants`]. The energy/wavelength pair is the
+//! same function as in [`crate::units`], re-exported under the names used
+//! here; XAFS k↔E and angle-unit helpers live in [`crate::units`].
+
+use crate::units;
+
+pub use crate::units::energy_to_wavelength_ang as energy_to_wavelength_angstrom;
+pub use crate::units::wavelength_to_energy_ev as wavelength_to_energy;
+
+/// Photon wavenumber 2π/λ in Å⁻¹ for an energy in eV.
+pub fn energy_to_wavenumber_invang(energy_ev: f64) -> f64 {
+    2.0 * std::f64::consts::PI / energy_to_wavelength_angstrom(energy_ev)
+}
+
+/// Absolute energy in eV of an XAFS point at photoelectron wavenumber `k`
+/// (Å⁻¹) above an edge at `e0` (eV).
+pub fn xafs_k_to_energy(e0: f64, k: f64) -> f64 {
+    e0 + units::ktoe(k)
+}
+
+/// Bragg angle θ in radians for reflection `order` from planes of spacing
+/// `d_spacing` (Å) at `energy` (eV), from nλ = 2d sin θ.
+///
+/// Returns `None` when the reflection is not reachable at this energy
+/// (nλ > 2d) or the inputs are not positive.
+pub fn bragg_angle(d_spacing: f64, energy: f64, order: u32) -> Option<f64> {
+    if !(d_spacing > 0.0 && energy > 0.0) || order == 0 {
+        return None;
+    }
+    let sin_theta = order as f64 * energy_to_wavelength_angstrom(energy) / (2.0 * d_spacing);
+    (sin_theta <= 1.0).then(|| sin_theta.asin())
+}
+
+/// Spacing in Å of the `(h, k, l)` planes of a cubic lattice with
+/// parameter `a` (Å). Returns infinity for `(0, 0, 0)`.
+pub fn d_spacing_cubic(a: f64, hkl: (i32, i32, i32)) -> f64 {
+    let (h, k, l) = hkl;
+    a / (f64::from(h).powi(2) + f64::from(k).powi(2) + f64::from(l).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codata_reference_values() {
+        assert!((energy_to_wavelength_angstrom(10000.0) - 1.2398).abs() < 1e-4);
+        assert!((wavelength_to_energy(1.2398) - 10000.0).abs() < 0.5);
+        assert!((energy_to_wavenumber_invang(10000.0) - 5.0677).abs() < 1e-4);
+        assert!((xafs_k_to_energy(7112.0, 10.0) - 7492.998).abs() < 1e-3);
+    }
+
+    #[test]
+    fn bragg_angles() {
+        let d111 = d_spacing_cubic(5.4309, (1, 1, 1));
+        assert!((d111 - 3.1355).abs() < 1e-4);
+        let theta = bragg_angle(d111, 8000.0, 1).unwrap();
+        assert!((theta.to_degrees() - 14.31).abs() < 0.01);
+
+        let theta3 = bragg_angle(d111, 24000.0, 3).unwrap();
+        assert!((theta3 - theta).abs() < 1e-12);
+
+        assert_eq!(bragg_angle(d111, 1000.0, 1), None);
+        assert_eq!(bragg_angle(d111, 8000.0, 0), None);
+        assert_eq!(d_spacing_cubic(5.43, (0, 0, 0)), f64::INFINITY);
+
+        // Would overflow an i32 sum of squares.
+        let d = d_spacing_cubic(5.43, (50000, 50000, 0));
+        assert!((d - 5.43 / (50000.0 * 2f64.sqrt())).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,174 @@
+//! Writers for dumping computed curves to CSV or JSON.
+//!
+//! Each curve is passed as a list of named columns, e.g.
+//! `&[("energy_eV", &energies), ("mu_cm2_per_g", &mu)]`. Both writers format
+//! values with `f64`'s `Debug` output: the shortest string that round-trips
+//! exactly, switching to exponent notation for very small or large
+//! magnitudes (`1e-20` rather than twenty decimal places).
+
+use std::io::{self, Write};
+
+fn check_columns(columns: &[(&str, &[f64])]) -> io::Result<usize> {
+    let nrows = columns.first().map_or(0, |(_, values)| values.len());
+    for (name, values) in columns {
+        if values.len() != nrows {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "column '{name}' has {} values, expected {nrows}",
+                    values.len()
+                ),
+            ));
+        }
+    }
+    Ok(nrows)
+}
+
+fn csv_field(name: &str) -> String {
+    if name.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    } else {
+        name.to_string()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn format_value(value: f64) -> String {
+    format!("{value:?}")
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format_value(value)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Write `columns` as CSV: one header row with the column names, then one
+/// row per sample. Non-finite values are written as `NaN`, `inf` or `-inf`.
+///
+/// All columns must have the same length; otherwise an
+/// [`io::ErrorKind::InvalidInput`] error is returned before anything is
+/// written.
+pub fn write_csv<W: Write>(mut writer: W, columns: &[(&str, &[f64])]) -> io::Result<()> {
+    let nrows = check_columns(columns)?;
+
+    let header: Vec<String> = columns.iter().map(|(name, _)| csv_field(name)).collect();
+    writeln!(writer, "{}", header.join(","))?;
+
+    for row in 0..nrows {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|(_, values)| format_value(values[row]))
+            .collect();
+        writeln!(writer, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Write `columns` as a JSON object mapping each column name to an array
+/// of numbers.
+///
+/// Non-finite values (NaN, ±inf) have no JSON representation and are
+/// written as `null`. Column lengths are checked as in [`write_csv`].
+pub fn write_json<W: Write>(mut writer: W, columns: &[(&str, &[f64])]) -> io::Result<()> {
+    check_columns(columns)?;
+
+    writeln!(writer, "{{")?;
+    for (i, (name, values)) in columns.iter().enumerate() {
+        let numbers: Vec<String> = values.iter().map(|&v| json_number(v)).collect();
+        let sep = if i + 1 < columns.len() { "," } else { "" };
+        writeln!(
+            writer,
+            "  {}: [{}]{sep}",
+            json_string(name),
+            numbers.join(", ")
+        )?;
+    }
+    writeln!(writer, "}}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_output() {
+        let energy = [7000.0, 7112.5];
+        let mu = [55.25, 1e-3];
+        let mut out = Vec::new();
+        write_csv(&mut out, &[("energy_eV", &energy), ("mu_cm2_per_g", &mu)]).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "energy_eV,mu_cm2_per_g\n7000.0,55.25\n7112.5,0.001\n"
+        );
+    }
+
+    #[test]
+    fn extreme_magnitudes_use_exponent_notation() {
+        let sigma = [1e-20, 1e-300, 2.5e22];
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &[("sigma_cm2", &sigma)]).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "sigma_cm2\n1e-20\n1e-300\n2.5e22\n"
+        );
+
+        let mut json = Vec::new();
+        write_json(&mut json, &[("sigma_cm2", &sigma)]).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "{\n  \"sigma_cm2\": [1e-20, 1e-300, 2.5e22]\n}\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_header_fields() {
+        let mut out = Vec::new();
+        write_csv(&mut out, &[("f1, \"Fe\"", &[1.5])]).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\"f1, \"\"Fe\"\"\"\n1.5\n");
+    }
+
+    #[test]
+    fn json_output() {
+        let theta = [0.001, 0.002];
+        let refl = [0.98, f64::NAN];
+        let mut out = Vec::new();
+        write_json(&mut out, &[("theta_rad", &theta), ("reflectivity", &refl)]).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\n  \"theta_rad\": [0.001, 0.002],\n  \"reflectivity\": [0.98, null]\n}\n"
+        );
+    }
+
+    #[test]
+    fn mismatched_columns_error() {
+        let mut out = Vec::new();
+        let err = write_csv(&mut out, &[("a", &[1.0, 2.0]), ("b", &[1.0])]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+
+        let err = write_json(&mut out, &[("a", &[1.0]), ("b", &[])]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+//! Reusable energy grids.
+//!
+//! [`EnergyGrid`] holds a sorted list of energies in eV that can be shared
+//! by every calculation of an experiment. A grid starts out log-spaced and
+//! can be refined with dense clusters of points around absorption edges.
+
+use crate::interp::interp_one;
+
+/// A non-empty, strictly increasing list of energies in eV.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyGrid {
+    energies: Vec<f64>,
+}
+
+impl EnergyGrid {
+    /// Log-spaced grid from `emin` to `emax` (both included) with at least
+    /// `pts_per_decade` points per factor of ten in energy.
+    ///
+    /// Returns `None` unless `0 < emin < emax` (both finite) and
+    /// `pts_per_decade > 0`.
+    pub fn log(emin: f64, emax: f64, pts_per_decade: usize) -> Option<Self> {
+        if !(emin > 0.0 && emin < emax && emax.is_finite()) || pts_per_decade == 0 {
+            return None;
+        }
+
+        let decades = (emax / emin).log10();
+        let nsteps = ((decades * pts_per_decade as f64).ceil() as usize).max(1);
+        let (lmin, lmax) = (emin.ln(), emax.ln());
+        let mut energies: Vec<f64> = (0..=nsteps)
+            .map(|i| (lmin + (lmax - lmin) * i as f64 / nsteps as f64).exp())
+            .collect();
+        // Pin the endpoints so they are exactly what the caller asked for.
+        energies[0] = emin;
+        energies[nsteps] = emax;
+        Some(Self { energies })
+    }
+
+    /// Build a grid from arbitrary energies, which are sorted and
+    /// deduplicated. Returns `None` if `energies` is empty or any energy is
+    /// not finite and positive.
+    pub fn from_energies(mut energies: Vec<f64>) -> Option<Self> {
+        if energies.is_empty() || energies.iter().any(|e| !(e.is_finite() && *e > 0.0)) {
+            return None;
+        }
+        energies.sort_by(f64::total_cmp);
+        energies.dedup();
+        Some(Self { energies })
+    }
+
+    /// Insert a geometric cluster of `extra_pts` points on each side of
+    /// every energy in `edges`, plus the edge energy itself.
+    ///
+    /// Offsets from the edge grow by a constant factor
+    /// `r = (window_ev / min_step_ev)^(1 / (extra_pts - 1))`, from
+    /// `min_step_ev` for the nearest point to `window_ev` for the farthest.
+    /// The local step at distance `d` from the edge is therefore about
+    /// `d * (1 - 1/r)`. `min_step_ev` is capped at `window_ev`; a
+    /// non-positive value puts every point at `window_ev`. Edges
+    /// outside the grid range are ignored and inserted points are kept
+    /// within it.
+    pub fn refine_near_energies(
+        &mut self,
+        edges: &[f64],
+        window_ev: f64,
+        min_step_ev: f64,
+        extra_pts: usize,
+    ) {
+        let (Some(&first), Some(&last)) = (self.energies.first(), self.energies.last()) else {
+            return;
+        };
+        let min_step = if min_step_ev > 0.0 {
+            min_step_ev.min(window_ev)
+        } else {
+            window_ev
+        };
+        let ratio = if extra_pts > 1 {
+            (window_ev / min_step).powf(1.0 / (extra_pts - 1) as f64)
+        } else {
+            1.0
+        };
+        let mut added = Vec::new();
+        for &edge in edges.iter().filter(|&&e| e >= first && e <= last) {
+            added.push(edge);
+            for k in 0..extra_pts {
+                let offset = if k + 1 == extra_pts {
+                    window_ev
+                } else {
+                    min_step * ratio.powi(k as i32)
+                };
+                added.extend([edge - offset, edge + offset]);
+            }
+        }
+        added.retain(|&e| e >= first && e <= last);
+        if added.is_empty() {
+            return;
+        }
+        self.energies.extend(added);
+        self.energies.sort_by(f64::total_cmp);
+        self.energies.dedup();
+    }
+
+    /// The grid energies in eV.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.energies
+    }
+
+    /// Number of grid points.
+    pub fn len(&self) -> usize {
+        self.energies.len()
+    }
+
+    /// True if the grid has no points. Grids are never empty, so this is
+    /// always false; it exists to pair with [`len`](Self::len).
+    pub fn is_empty(&self) -> bool {
+        self.energies.is_empty()
+    }
+
+    /// Index of the grid point nearest to `energy`, or `None` if `energy`
+    /// lies outside the grid range.
+    pub fn index_of(&self, energy: f64) -> Option<usize> {
+        let (&first, &last) = (self.energies.first()?, self.energies.last()?);
+        if !(energy >= first && energy <= last) {
+            return None;
+        }
+        let upper = self.energies.partition_point(|&e| e < energy);
+        if upper == 0 {
+            return Some(0);
+        }
+        let lower = upper - 1;
+        if energy - self.energies[lower] <= self.energies[upper] - energy {
+            Some(lower)
+        } else {
+            Some(upper)
+        }
+    }
+
+    /// Resample `values` (one per point of this grid) onto `coarser`.
+    ///
+    /// Each point of `coarser` owns the energies closer to it than to its
+    /// neighbours (the outermost bins are as wide on the outside as on the
+    /// inside); the result is the mean of the values of this grid that
+    /// fall in that bin. Bins containing no points of this grid fall back to
+    /// linear interpolation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values.len() != self.len()`.
+    pub fn rebin(&self, values: &[f64], coarser: &EnergyGrid) -> Vec<f64> {
+        assert_eq!(
+            values.len(),
+            self.len(),
+            "rebin needs one value per grid point"
+        );
+        let target = &coarser.energies;
+        let mut out = Vec::with_capacity(target.len());
+        for (i, &center) in target.iter().enumerate() {
+            // Outer bins mirror the half-width of their only neighbour.
+            let lo = match (i.checked_sub(1), target.get(i + 1)) {
+                (Some(prev), _) => 0.5 * (target[prev] + center),
+                (None, Some(&next)) => center - 0.5 * (next - center),
+                (None, None) => f64::NEG_INFINITY,
+            };
+            let hi = match (target.get(i + 1), i.checked_sub(1)) {
+                (Some(&next), _) => 0.5 * (center + next),
+                (None, Some(prev)) => center + 0.5 * (center - target[prev]),
+                (None, None) => f64::INFINITY,
+            };
+            let start = self.energies.partition_point(|&e| e < lo);
+            let end = self.energies.partition_point(|&e| e < hi);
+            if end > start {
+                let sum: f64 = values[start..end].iter().sum();
+                out.push(sum / (end - start) as f64);
+            } else {
+                out.push(interp_one(center, &self.energies, values));
+            }
+        }
+        out
+    }
+}
+
+impl AsRef<[f64]> for EnergyGrid {
+    fn as_ref(&self) -> &[f64] {
+        &self.energies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_grid_endpoints_and_spacing() {
+        let grid = EnergyGrid::log(1000.0, 100000.0, 50).unwrap();
+        assert_eq!(grid.len(), 101);
+        assert_eq!(grid.as_slice()[0], 1000.0);
+        assert_eq!(grid.as_slice()[100], 100000.0);
+        let e = grid.as_slice();
+        let r0 = e[1] / e[0];
+        let r1 = e[51] / e[50];
+        assert!((r0 - r1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn refine_resolves_fe2o3_edges() {
+        // Fe2O3: O K at 543.1 eV, Fe L3 at 708.1 eV and Fe K at 7112 eV.
+        let mut grid = EnergyGrid::log(500.0, 30000.0, 200).unwrap();
+        let edges = [543.1, 708.1, 7112.0];
+        grid.refine_near_energies(&edges, 50.0, 0.1, 200);
+        assert!(grid.len() < 3000, "grid has {} points", grid.len());
+
+        let e = grid.as_slice();
+        assert!(e.windows(2).all(|w| w[0] < w[1]));
+        for edge in edges {
+            let start = grid.index_of(edge - 20.0).unwrap();
+            let end = grid.index_of(edge + 30.0).unwrap();
+            assert!(e.contains(&edge), "no point at {edge}");
+            assert!(
+                e[start..=end].windows(2).all(|w| w[1] - w[0] <= 1.0),
+                "spacing above 1 eV near {edge}"
+            );
+        }
+    }
+
+    #[test]
+    fn refine_clusters_are_geometric() {
+        let mut grid = EnergyGrid::log(1000.0, 10000.0, 1).unwrap();
+        grid.refine_near_energies(&[5000.0], 100.0, 1.0, 5);
+        let e = grid.as_slice();
+        let i = grid.index_of(5000.0).unwrap();
+        let above: Vec<f64> = e[i + 1..i + 6].iter().map(|v| v - 5000.0).collect();
+        let below: Vec<f64> = e[i - 5..i].iter().rev().map(|v| 5000.0 - v).collect();
+        // r = 100^(1/4): offsets 1, 3.16, 10, 31.6, 100 eV on both sides.
+        for offsets in [above, below] {
+            assert!((offsets[0] - 1.0).abs() < 1e-9);
+            assert!((offsets[4] - 100.0).abs() < 1e-9);
+            for w in offsets.windows(2) {
+                assert!((w[1] / w[0] - 10f64.sqrt()).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_grids_are_rejected() {
+        assert!(EnergyGrid::log(0.0, 1000.0, 10).is_none());
+        assert!(EnergyGrid::log(2000.0, 1000.0, 10).is_none());
+        assert!(EnergyGrid::log(1000.0, f64::INFINITY, 10).is_none());
+        assert!(EnergyGrid::log(1000.0, 2000.0, 0).is_none());
+        assert!(EnergyGrid::from_energies(Vec::new()).is_none());
+        assert!(EnergyGrid::from_energies(vec![1.0, -2.0]).is_none());
+        assert!(EnergyGrid::from_energies(vec![1.0, f64::NAN]).is_none());
+    }
+
+    #[test]
+    fn index_of_nearest() {
+        let grid = EnergyGrid::from_energies(vec![3.0, 1.0, 2.0, 2.0]).unwrap();
+        assert_eq!(grid.as_slice(), &[1.0, 2.0, 3.0]);
+        assert_eq!(grid.index_of(1.0), Some(0));
+        assert_eq!(grid.index_of(1.4), Some(0));
+        assert_eq!(grid.index_of(1.6), Some(1));
+        assert_eq!(grid.index_of(3.0), Some(2));
+        assert_eq!(grid.index_of(0.5), None);
+        assert_eq!(grid.index_of(f64::NAN), None);
+    }
+
+    #[test]
+    fn rebin_averages_and_interpolates() {
+        let fine = EnergyGrid::from_energies(vec![1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+        let values = [10.0, 20.0, 30.0, 40.0, 50.0];
+
+        let coarse = EnergyGrid::from_energies(vec![1.5, 4.5]).unwrap();
+        assert_eq!(fine.rebin(&values, &coarse), vec![15.0, 40.0]);
+
+        let dense = EnergyGrid::from_energies(vec![2.0, 2.25, 2.5]).unwrap();
+        assert_eq!(fine.rebin(&values, &dense), vec![20.0, 22.5, 25.0]);
+    }
+}
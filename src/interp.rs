@@ -0,0 +1,473 @@
+//! Interpolation on tabulated data.
+//!
+//! All functions follow `numpy.interp` semantics: `xp` must be increasing,
+//! points outside the table take the first or last value of `fp`, and a
+//! NaN query point gives NaN. Log-log interpolation also gives NaN for
+//! non-positive query points, including zero.
+//!
+//! The `_into` variants and [`LogTable`] write into a caller-provided
+//! buffer and never allocate, for use in hot loops. [`pchip`] offers a
+//! monotone piecewise-cubic alternative to linear interpolation, and
+//! [`interpolate`] selects between the methods with [`InterpMethod`].
+//!
+//! With the `parallel` feature, evaluating at least
+//! [`PARALLEL_THRESHOLD`] points is spread over the rayon thread pool.
+//! Every point is computed independently, so results are identical to the
+//! serial path.
+
+use crate::spline::{BoundaryCondition, Spline};
+
+/// Input length from which the `parallel` feature evaluates points on the
+/// rayon thread pool; shorter inputs stay serial to avoid the overhead.
+pub const PARALLEL_THRESHOLD: usize = 4096;
+
+/// Write `f(x[i])` to `out[i]` for every point, in parallel for long inputs
+/// when the `parallel` feature is enabled.
+pub(crate) fn map_into<F>(x: &[f64], out: &mut [f64], f: F)
+where
+    F: Fn(f64) -> f64 + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if x.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        out.par_iter_mut()
+            .zip(x.par_iter())
+            .for_each(|(o, &xi)| *o = f(xi));
+        return;
+    }
+    for (o, &xi) in out.iter_mut().zip(x) {
+        *o = f(xi);
+    }
+}
+
+/// Interpolate a single point.
+///
+/// # Panics
+///
+/// Panics if `xp` is empty or `xp` and `fp` differ in length.
+pub fn interp_one(x: f64, xp: &[f64], fp: &[f64]) -> f64 {
+    assert!(!xp.is_empty(), "interpolation table is empty");
+    assert_eq!(xp.len(), fp.len(), "xp and fp differ in length");
+
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    let last = xp.len() - 1;
+    if x <= xp[0] {
+        return fp[0];
+    }
+    if x >= xp[last] {
+        return fp[last];
+    }
+    let upper = xp.partition_point(|&v| v <= x);
+    let lower = upper - 1;
+    let t = (x - xp[lower]) / (xp[upper] - xp[lower]);
+    fp[lower] + t * (fp[upper] - fp[lower])
+}
+
+/// Interpolate every point of `x` into `out`.
+///
+/// # Panics
+///
+/// Panics if `out.len() != x.len()`, or as for [`interp_one`].
+pub fn interp_into(x: &[f64], xp: &[f64], fp: &[f64], out: &mut [f64]) {
+    assert_eq!(x.len(), out.len(), "output buffer has the wrong length");
+    map_into(x, out, |xi| interp_one(xi, xp, fp));
+}
+
+/// Interpolate every point of `x`, returning a new vector.
+pub fn interp(x: &[f64], xp: &[f64], fp: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; x.len()];
+    interp_into(x, xp, fp, &mut out);
+    out
+}
+
+/// Log-log interpolation from precomputed logarithms:
+/// `out[i] = exp(interp(log_x[i], log_xp, log_fp))`.
+///
+/// # Panics
+///
+/// As for [`interp_into`].
+pub fn interp_loglog_precomputed(log_x: &[f64], log_xp: &[f64], log_fp: &[f64], out: &mut [f64]) {
+    assert_eq!(log_x.len(), out.len(), "output buffer has the wrong length");
+    map_into(log_x, out, |lx| interp_one(lx, log_xp, log_fp).exp());
+}
+
+/// `ln(x)` for positive `x`, NaN otherwise. `ln(0)` is `-inf`, which would
+/// otherwise be clamped to the first table value instead of flagged.
+fn log_or_nan(x: f64) -> f64 {
+    if x > 0.0 {
+        x.ln()
+    } else {
+        f64::NAN
+    }
+}
+
+/// Log-log interpolation: linear in `ln(x)` versus `ln(fp)`.
+///
+/// `xp` and `fp` must be positive; query points that are NaN, zero or
+/// negative give NaN. When the same table is evaluated repeatedly, build a
+/// [`LogTable`] once instead.
+pub fn interp_loglog(x: &[f64], xp: &[f64], fp: &[f64]) -> Vec<f64> {
+    let log_xp: Vec<f64> = xp.iter().map(|v| v.ln()).collect();
+    let log_fp: Vec<f64> = fp.iter().map(|v| v.ln()).collect();
+    let log_x: Vec<f64> = x.iter().map(|&v| log_or_nan(v)).collect();
+    let mut out = vec![0.0; x.len()];
+    interp_loglog_precomputed(&log_x, &log_xp, &log_fp, &mut out);
+    out
+}
+
+/// A table prepared for repeated log-log interpolation.
+///
+/// Holds `ln(xp)` and `ln(fp)` so evaluation only takes the logarithm of
+/// the query points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogTable {
+    log_xp: Vec<f64>,
+    log_fp: Vec<f64>,
+}
+
+impl LogTable {
+    /// Precompute the logarithms of a positive table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `xp` is empty or `xp` and `fp` differ in length.
+    pub fn new(xp: &[f64], fp: &[f64]) -> Self {
+        assert!(!xp.is_empty(), "interpolation table is empty");
+        assert_eq!(xp.len(), fp.len(), "xp and fp differ in length");
+        Self {
+            log_xp: xp.iter().map(|v| v.ln()).collect(),
+            log_fp: fp.iter().map(|v| v.ln()).collect(),
+        }
+    }
+
+    /// `ln(xp)` of the table.
+    pub fn log_xp(&self) -> &[f64] {
+        &self.log_xp
+    }
+
+    /// `ln(fp)` of the table.
+    pub fn log_fp(&self) -> &[f64] {
+        &self.log_fp
+    }
+
+    /// Evaluate at a single point; NaN if `x` is NaN or not positive.
+    pub fn eval_one(&self, x: f64) -> f64 {
+        interp_one(log_or_nan(x), &self.log_xp, &self.log_fp).exp()
+    }
+
+    /// Evaluate every point of `x` into `out` without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len() != x.len()`.
+    pub fn eval_into(&self, x: &[f64], out: &mut [f64]) {
+        assert_eq!(x.len(), out.len(), "output buffer has the wrong length");
+        map_into(x, out, |xi| self.eval_one(xi));
+    }
+
+    /// Evaluate every point of `x`, returning a new vector.
+    pub fn eval(&self, x: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; x.len()];
+        self.eval_into(x, &mut out);
+        out
+    }
+}
+
+/// Choice of interpolation scheme for tabulated data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterpMethod {
+    /// Piecewise linear, as [`interp`].
+    #[default]
+    Linear,
+    /// Monotone piecewise cubic, as [`pchip`].
+    Pchip,
+    /// Natural cubic spline, as [`Spline`].
+    Spline,
+}
+
+/// Interpolate every point of `x` with the chosen method.
+pub fn interpolate(method: InterpMethod, x: &[f64], xp: &[f64], fp: &[f64]) -> Vec<f64> {
+    match method {
+        InterpMethod::Linear => interp(x, xp, fp),
+        InterpMethod::Pchip => pchip(x, xp, fp),
+        InterpMethod::Spline if xp.len() < 2 => interp(x, xp, fp),
+        InterpMethod::Spline => Spline::new(xp, fp, BoundaryCondition::Natural).eval(x),
+    }
+}
+
+/// Monotone piecewise-cubic Hermite interpolation (PCHIP).
+///
+/// Knot derivatives follow Fritsch & Butland as in SciPy's
+/// `PchipInterpolator`: the curve never overshoots the values of the
+/// bracketing knots, so steps such as absorption edges do not ring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PchipInterpolator {
+    xp: Vec<f64>,
+    fp: Vec<f64>,
+    slopes: Vec<f64>,
+}
+
+impl PchipInterpolator {
+    /// Build the interpolant through `(xp, fp)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `xp` is empty, `xp` and `fp` differ in length, or `xp` is
+    /// not strictly increasing.
+    pub fn new(xp: &[f64], fp: &[f64]) -> Self {
+        assert!(!xp.is_empty(), "interpolation table is empty");
+        assert_eq!(xp.len(), fp.len(), "xp and fp differ in length");
+        assert!(
+            xp.windows(2).all(|w| w[0] < w[1]),
+            "xp must be strictly increasing"
+        );
+        Self {
+            xp: xp.to_vec(),
+            fp: fp.to_vec(),
+            slopes: pchip_slopes(xp, fp),
+        }
+    }
+
+    /// Evaluate at a single point.
+    pub fn eval_one(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        let (xp, fp, d) = (&self.xp, &self.fp, &self.slopes);
+        let last = xp.len() - 1;
+        if x <= xp[0] {
+            return fp[0];
+        }
+        if x >= xp[last] {
+            return fp[last];
+        }
+        let hi = xp.partition_point(|&v| v <= x);
+        let lo = hi - 1;
+        let h = xp[hi] - xp[lo];
+        let t = (x - xp[lo]) / h;
+        let t2 = t * t;
+        let t3 = t2 * t;
+        (2.0 * t3 - 3.0 * t2 + 1.0) * fp[lo]
+            + (t3 - 2.0 * t2 + t) * h * d[lo]
+            + (-2.0 * t3 + 3.0 * t2) * fp[hi]
+            + (t3 - t2) * h * d[hi]
+    }
+
+    /// Evaluate every point of `x` into `out` without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out.len() != x.len()`.
+    pub fn eval_into(&self, x: &[f64], out: &mut [f64]) {
+        assert_eq!(x.len(), out.len(), "output buffer has the wrong length");
+        map_into(x, out, |xi| self.eval_one(xi));
+    }
+
+    /// Evaluate every point of `x`, returning a new vector.
+    pub fn eval(&self, x: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; x.len()];
+        self.eval_into(x, &mut out);
+        out
+    }
+}
+
+/// Monotone piecewise-cubic interpolation of every point of `x`.
+///
+/// See [`PchipInterpolator`]; build one directly when the same table is
+/// evaluated repeatedly.
+pub fn pchip(x: &[f64], xp: &[f64], fp: &[f64]) -> Vec<f64> {
+    PchipInterpolator::new(xp, fp).eval(x)
+}
+
+fn pchip_slopes(xp: &[f64], fp: &[f64]) -> Vec<f64> {
+    let n = xp.len();
+    if n == 1 {
+        return vec![0.0];
+    }
+    let h: Vec<f64> = xp.windows(2).map(|w| w[1] - w[0]).collect();
+    let m: Vec<f64> = fp
+        .windows(2)
+        .zip(&h)
+        .map(|(w, hk)| (w[1] - w[0]) / hk)
+        .collect();
+    if n == 2 {
+        return vec![m[0], m[0]];
+    }
+
+    let mut d = vec![0.0; n];
+    for k in 1..n - 1 {
+        // Compare signs directly: the product of two tiny slopes underflows.
+        if m[k - 1] == 0.0 || m[k] == 0.0 || m[k - 1].signum() != m[k].signum() {
+            continue;
+        }
+        // Weighted harmonic mean of the neighbouring secant slopes.
+        let w1 = 2.0 * h[k] + h[k - 1];
+        let w2 = h[k] + 2.0 * h[k - 1];
+        d[k] = (w1 + w2) / (w1 / m[k - 1] + w2 / m[k]);
+    }
+    d[0] = pchip_end_slope(h[0], h[1], m[0], m[1]);
+    d[n - 1] = pchip_end_slope(h[n - 2], h[n - 3], m[n - 2], m[n - 3]);
+    d
+}
+
+/// Shape-preserving three-point estimate of an end derivative.
+fn pchip_end_slope(h0: f64, h1: f64, m0: f64, m1: f64) -> f64 {
+    let d = ((2.0 * h0 + h1) * m0 - h0 * m1) / (h0 + h1);
+    if d.signum() != m0.signum() || m0 == 0.0 {
+        0.0
+    } else if m0.signum() != m1.signum() && d.abs() > 3.0 * m0.abs() {
+        3.0 * m0
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XP: [f64; 4] = [1.0, 2.0, 4.0, 8.0];
+    const FP: [f64; 4] = [10.0, 5.0, 2.5, 1.0];
+
+    #[test]
+    fn linear_matches_numpy_semantics() {
+        let x = [0.5, 1.0, 1.5, 3.0, 8.0, 9.0];
+        assert_eq!(interp(&x, &XP, &FP), vec![10.0, 10.0, 7.5, 3.75, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn into_matches_allocating() {
+        let x = [1.2, 2.7, 5.5, 7.9];
+        let mut out = [0.0; 4];
+        interp_into(&x, &XP, &FP, &mut out);
+        assert_eq!(out.to_vec(), interp(&x, &XP, &FP));
+    }
+
+    #[test]
+    fn loglog_variants_agree() {
+        let x = [0.5, 1.0, 1.3, 3.0, 6.1, 8.0, 20.0];
+        let expected = interp_loglog(&x, &XP, &FP);
+
+        let table = LogTable::new(&XP, &FP);
+        let mut out = [0.0; 7];
+        table.eval_into(&x, &mut out);
+        assert_eq!(out.to_vec(), expected);
+        assert_eq!(table.eval(&x), expected);
+
+        let log_x: Vec<f64> = x.iter().map(|&v| log_or_nan(v)).collect();
+        let mut out = [0.0; 7];
+        interp_loglog_precomputed(&log_x, table.log_xp(), table.log_fp(), &mut out);
+        assert_eq!(out.to_vec(), expected);
+    }
+
+    #[test]
+    fn nan_and_non_positive_points_give_nan() {
+        assert!(interp_one(f64::NAN, &XP, &FP).is_nan());
+        let linear = interp(&[1.5, f64::NAN, 3.0], &XP, &FP);
+        assert_eq!(linear[0], 7.5);
+        assert!(linear[1].is_nan());
+        assert_eq!(linear[2], 3.75);
+
+        let table = LogTable::new(&XP, &FP);
+        assert!(table.eval_one(f64::NAN).is_nan());
+        assert!(table.eval_one(-5.0).is_nan());
+        assert!(table.eval_one(0.0).is_nan());
+        assert!(table.eval_one(-0.0).is_nan());
+        let mut out = [0.0; 3];
+        table.eval_into(&[2.0, -5.0, f64::NAN], &mut out);
+        assert!((out[0] - 5.0).abs() < 1e-12);
+        assert!(out[1].is_nan() && out[2].is_nan());
+        assert!(interp_loglog(&[-5.0, 0.0], &XP, &FP)
+            .iter()
+            .all(|v| v.is_nan()));
+    }
+
+    #[test]
+    fn loglog_is_exact_for_power_laws() {
+        // fp = 10 / xp is a straight line in log-log space.
+        let xp = [1.0, 10.0, 100.0];
+        let fp = [10.0, 1.0, 0.1];
+        let got = interp_loglog(&[3.0, 50.0], &xp, &fp);
+        assert!((got[0] - 10.0 / 3.0).abs() < 1e-12);
+        assert!((got[1] - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pchip_does_not_overshoot_steps() {
+        let xp = [0.0, 1.0, 2.0, 2.1, 3.0, 4.0];
+        let fp = [1.0, 1.0, 1.2, 5.0, 5.1, 5.1];
+        let x: Vec<f64> = (0..=400).map(|i| i as f64 * 0.01).collect();
+        let y = pchip(&x, &xp, &fp);
+        for (&xi, &yi) in x.iter().zip(&y) {
+            let hi = xp.partition_point(|&v| v <= xi).clamp(1, xp.len() - 1);
+            let (a, b) = (fp[hi - 1], fp[hi]);
+            assert!(
+                yi >= a.min(b) - 1e-12 && yi <= a.max(b) + 1e-12,
+                "overshoot at {xi}: {yi}"
+            );
+        }
+        // The natural spline rings on the same data.
+        let spline = interpolate(InterpMethod::Spline, &x, &xp, &fp);
+        assert!(spline
+            .iter()
+            .any(|v| !(1.0 - 1e-3..=5.1 + 1e-3).contains(v)));
+    }
+
+    #[test]
+    fn pchip_matches_linear_on_lines() {
+        let xp = [0.0, 1.0, 3.0, 3.5, 7.0];
+        let fp: Vec<f64> = xp.iter().map(|v| 2.0 * v + 1.0).collect();
+        let x = [-1.0, 0.25, 2.0, 3.2, 6.9, 8.0];
+        let linear = interpolate(InterpMethod::Linear, &x, &xp, &fp);
+        let monotone = interpolate(InterpMethod::Pchip, &x, &xp, &fp);
+        for (a, b) in linear.iter().zip(&monotone) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert_eq!(InterpMethod::default(), InterpMethod::Linear);
+
+        // Slopes whose product underflows to zero are still a line.
+        let tiny = pchip(&[0.5], &[0.0, 1.0, 2.0], &[0.0, 1e-200, 2e-200]);
+        assert!((tiny[0] / 5e-201 - 1.0).abs() < 1e-12, "got {:e}", tiny[0]);
+    }
+
+    #[test]
+    fn pchip_hits_knots_and_handles_short_tables() {
+        let xp = [1.0, 2.0, 4.0];
+        let fp = [3.0, -1.0, 2.0];
+        assert_eq!(pchip(&xp, &xp, &fp), fp.to_vec());
+        assert_eq!(
+            pchip(&[0.0, 1.5, 9.0], &[1.0, 2.0], &[0.0, 2.0]),
+            vec![0.0, 1.0, 2.0]
+        );
+        assert_eq!(pchip(&[0.0, 5.0], &[1.0], &[7.0]), vec![7.0, 7.0]);
+
+        let x = [f64::NAN, 1.5];
+        let monotone = interpolate(InterpMethod::Pchip, &x, &xp, &fp);
+        let spline = interpolate(InterpMethod::Spline, &x, &xp, &fp);
+        assert!(monotone[0].is_nan() && spline[0].is_nan());
+        assert!(monotone[1].is_finite() && spline[1].is_finite());
+    }
+
+    #[test]
+    fn long_inputs_match_pointwise_evaluation() {
+        // Long enough to take the parallel path when that feature is on.
+        let xp: Vec<f64> = (1..=50).map(|i| i as f64 * 100.0).collect();
+        let fp: Vec<f64> = xp.iter().map(|v| 1e6 / (v * v)).collect();
+        let x: Vec<f64> = (0..2 * PARALLEL_THRESHOLD)
+            .map(|i| 50.0 + i as f64 * 0.6)
+            .collect();
+
+        let linear = interp(&x, &xp, &fp);
+        let table = LogTable::new(&xp, &fp);
+        let loglog = table.eval(&x);
+        let monotone = PchipInterpolator::new(&xp, &fp);
+        let cubic = monotone.eval(&x);
+        for (i, &xi) in x.iter().enumerate() {
+            assert_eq!(linear[i], interp_one(xi, &xp, &fp));
+            assert_eq!(loglog[i], table.eval_one(xi));
+            assert_eq!(cubic[i], monotone.eval_one(xi));
+        }
+    }
+}
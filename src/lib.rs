@@ -1,3 +1,16 @@
+pub mod constants;
+pub mod convert;
+pub mod export;
+pub mod grid;
+pub mod interp;
+pub mod spline;
+pub mod units;
+
+pub use convert::{
+    bragg_angle, d_spacing_cubic, energy_to_wavelength_angstrom, energy_to_wavenumber_invang,
+    wavelength_to_energy, xafs_k_to_energy,
+};
+
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
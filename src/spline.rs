@@ -0,0 +1,208 @@
+//! Cubic splines in the form used by the Elam tables.
+//!
+//! A spline is stored as knots `x`, values `y` and the second derivatives
+//! `y2` at each knot. [`elam_spline`] evaluates such a spline, and
+//! [`cubic_spline_coefficients`] builds `y2` for new data so that it can be
+//! evaluated with exactly the same scheme as the tabulated curves.
+
+use crate::interp::map_into;
+
+/// End conditions for [`cubic_spline_coefficients`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundaryCondition {
+    /// Zero second derivative at both ends.
+    Natural,
+    /// Prescribed first derivatives at the first and last knot.
+    Clamped(f64, f64),
+}
+
+fn check_table(x: &[f64], y: &[f64]) {
+    assert!(x.len() >= 2, "a spline needs at least two knots");
+    assert_eq!(x.len(), y.len(), "x and y differ in length");
+    assert!(
+        x.windows(2).all(|w| w[0] < w[1]),
+        "spline knots must be strictly increasing"
+    );
+}
+
+/// Second derivatives of the interpolating cubic spline through `(x, y)`.
+///
+/// The result can be passed as `y2` to [`elam_spline`].
+///
+/// # Panics
+///
+/// Panics if there are fewer than two knots, `x` and `y` differ in length,
+/// or `x` is not strictly increasing.
+pub fn cubic_spline_coefficients(x: &[f64], y: &[f64], bc: BoundaryCondition) -> Vec<f64> {
+    check_table(x, y);
+    let n = x.len();
+    let mut y2 = vec![0.0; n];
+    let mut u = vec![0.0; n];
+
+    if let BoundaryCondition::Clamped(yp1, _) = bc {
+        y2[0] = -0.5;
+        u[0] = (3.0 / (x[1] - x[0])) * ((y[1] - y[0]) / (x[1] - x[0]) - yp1);
+    }
+
+    // Forward sweep of the tridiagonal system.
+    for i in 1..n - 1 {
+        let sig = (x[i] - x[i - 1]) / (x[i + 1] - x[i - 1]);
+        let p = sig * y2[i - 1] + 2.0;
+        y2[i] = (sig - 1.0) / p;
+        let slope_diff =
+            (y[i + 1] - y[i]) / (x[i + 1] - x[i]) - (y[i] - y[i - 1]) / (x[i] - x[i - 1]);
+        u[i] = (6.0 * slope_diff / (x[i + 1] - x[i - 1]) - sig * u[i - 1]) / p;
+    }
+
+    let (qn, un) = match bc {
+        BoundaryCondition::Natural => (0.0, 0.0),
+        BoundaryCondition::Clamped(_, ypn) => {
+            let h = x[n - 1] - x[n - 2];
+            (0.5, (3.0 / h) * (ypn - (y[n - 1] - y[n - 2]) / h))
+        }
+    };
+    y2[n - 1] = (un - qn * u[n - 2]) / (qn * y2[n - 2] + 1.0);
+
+    // Back substitution.
+    for k in (0..n - 1).rev() {
+        y2[k] = y2[k] * y2[k + 1] + u[k];
+    }
+    y2
+}
+
+fn eval_one(x: &[f64], y: &[f64], y2: &[f64], xout: f64) -> f64 {
+    let last = x.len() - 1;
+    let xv = xout.clamp(x[0], x[last]);
+    let hi = x.partition_point(|&v| v < xv).clamp(1, last);
+    let lo = hi - 1;
+    let h = x[hi] - x[lo];
+    let a = (x[hi] - xv) / h;
+    let b = (xv - x[lo]) / h;
+    a * y[lo]
+        + b * y[hi]
+        + (h * h / 6.0) * ((a * a - 1.0) * a * y2[lo] + (b * b - 1.0) * b * y2[hi])
+}
+
+/// Evaluate the cubic spline `(x, y, y2)` at each point of `xout`.
+///
+/// Points outside the knot range are clamped to the nearest end, as in the
+/// reference xraydb implementation.
+///
+/// # Panics
+///
+/// Panics if the table is invalid (see [`cubic_spline_coefficients`]) or
+/// `y2` differs in length from `x`.
+pub fn elam_spline(x: &[f64], y: &[f64], y2: &[f64], xout: &[f64]) -> Vec<f64> {
+    check_table(x, y);
+    assert_eq!(x.len(), y2.len(), "x and y2 differ in length");
+    let mut out = vec![0.0; xout.len()];
+    map_into(xout, &mut out, |xv| eval_one(x, y, y2, xv));
+    out
+}
+
+/// A cubic spline bundling its knots, values and second derivatives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    y2: Vec<f64>,
+}
+
+impl Spline {
+    /// Build the interpolating spline through `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// As for [`cubic_spline_coefficients`].
+    pub fn new(x: &[f64], y: &[f64], bc: BoundaryCondition) -> Self {
+        let y2 = cubic_spline_coefficients(x, y, bc);
+        Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            y2,
+        }
+    }
+
+    /// Knot positions.
+    pub fn knots(&self) -> &[f64] {
+        &self.x
+    }
+
+    /// Values at the knots.
+    pub fn values(&self) -> &[f64] {
+        &self.y
+    }
+
+    /// Second derivatives at the knots.
+    pub fn second_derivatives(&self) -> &[f64] {
+        &self.y2
+    }
+
+    /// Evaluate at a single point, clamping outside the knot range.
+    pub fn eval_one(&self, x: f64) -> f64 {
+        eval_one(&self.x, &self.y, &self.y2, x)
+    }
+
+    /// Evaluate at each point of `x`, clamping outside the knot range.
+    pub fn eval(&self, x: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; x.len()];
+        map_into(x, &mut out, |xv| self.eval_one(xv));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn natural_spline_of_line_is_exact() {
+        let x = [0.0, 1.0, 2.5, 4.0];
+        let y: Vec<f64> = x.iter().map(|v| 3.0 * v - 1.0).collect();
+        let y2 = cubic_spline_coefficients(&x, &y, BoundaryCondition::Natural);
+        assert!(y2.iter().all(|v| v.abs() < 1e-12));
+        let got = elam_spline(&x, &y, &y2, &[0.5, 3.2]);
+        assert!((got[0] - 0.5).abs() < 1e-12);
+        assert!((got[1] - 8.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn clamped_spline_reproduces_cubic() {
+        let f = |v: f64| v * v * v - 2.0 * v * v + 0.5;
+        let df = |v: f64| 3.0 * v * v - 4.0 * v;
+        let x = [-1.0, 0.0, 0.7, 1.5, 3.0];
+        let y: Vec<f64> = x.iter().map(|&v| f(v)).collect();
+        let spline = Spline::new(&x, &y, BoundaryCondition::Clamped(df(-1.0), df(3.0)));
+        for xv in [-0.5, 0.3, 1.1, 2.2, 2.9] {
+            assert!((spline.eval_one(xv) - f(xv)).abs() < 1e-12, "at {xv}");
+        }
+        // Second derivative of the cubic is 6x - 4.
+        for (xv, y2) in x.iter().zip(spline.second_derivatives()) {
+            assert!((y2 - (6.0 * xv - 4.0)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn natural_spline_of_sine() {
+        let x: Vec<f64> = (0..=20)
+            .map(|i| i as f64 * std::f64::consts::PI / 20.0)
+            .collect();
+        let y: Vec<f64> = x.iter().map(|v| v.sin()).collect();
+        let spline = Spline::new(&x, &y, BoundaryCondition::Natural);
+        let xs = [0.1, 0.9, 1.6, 2.75];
+        for (xv, got) in xs.iter().zip(spline.eval(&xs)) {
+            assert!((got - xv.sin()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn evaluation_clamps_and_hits_knots() {
+        let x = [1.0, 2.0, 3.0];
+        let y = [2.0, 0.5, 4.0];
+        let spline = Spline::new(&x, &y, BoundaryCondition::Natural);
+        assert_eq!(
+            spline.eval(&[0.0, 1.0, 2.0, 3.0, 9.0]),
+            vec![2.0, 2.0, 0.5, 4.0, 4.0]
+        );
+    }
+}
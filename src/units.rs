@@ -0,0 +1,79 @@
+//! Energy, wavelength, photoelectron wavenumber and angle conversions.
+//!
+//! Energies are in eV, wavelengths in Å and XAFS wavenumbers in Å⁻¹,
+//! matching the conventions used throughout the crate. Photon wavenumber,
+//! Bragg-angle and d-spacing conversions are in [`crate::convert`].
+
+use crate::constants::{KTOE, PLANCK_HC_EV_ANGSTROM};
+
+/// Photon wavelength in Å for an energy in eV.
+pub fn energy_to_wavelength_ang(energy_ev: f64) -> f64 {
+    PLANCK_HC_EV_ANGSTROM / energy_ev
+}
+
+/// Photon energy in eV for a wavelength in Å.
+pub fn wavelength_to_energy_ev(wavelength_ang: f64) -> f64 {
+    PLANCK_HC_EV_ANGSTROM / wavelength_ang
+}
+
+/// Photoelectron wavenumber k in Å⁻¹ for an energy above the edge, E - E0,
+/// in eV. Energies below the edge give the negative of the wavenumber for
+/// |E - E0|, so pre-edge points stay ordered.
+pub fn etok(e_rel_ev: f64) -> f64 {
+    e_rel_ev.signum() * (e_rel_ev.abs() / KTOE).sqrt()
+}
+
+/// Energy above the edge, E - E0, in eV for a photoelectron wavenumber in
+/// Å⁻¹.
+pub fn ktoe(k_inv_ang: f64) -> f64 {
+    KTOE * k_inv_ang * k_inv_ang
+}
+
+/// Degrees to radians.
+pub fn deg_to_rad(deg: f64) -> f64 {
+    deg.to_radians()
+}
+
+/// Radians to degrees.
+pub fn rad_to_deg(rad: f64) -> f64 {
+    rad.to_degrees()
+}
+
+/// Milliradians to radians.
+pub fn mrad_to_rad(mrad: f64) -> f64 {
+    mrad * 1e-3
+}
+
+/// Radians to milliradians.
+pub fn rad_to_mrad(rad: f64) -> f64 {
+    rad * 1e3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wavelength_round_trip() {
+        assert!((energy_to_wavelength_ang(10000.0) - 1.23984).abs() < 1e-5);
+        assert!((wavelength_to_energy_ev(1.5406) - 8047.8).abs() < 0.1);
+        let e = 17479.3;
+        assert!((wavelength_to_energy_ev(energy_to_wavelength_ang(e)) - e).abs() < 1e-9);
+    }
+
+    #[test]
+    fn xafs_wavenumber() {
+        assert!((etok(100.0) - 5.12).abs() < 0.01);
+        assert!((ktoe(etok(250.0)) - 250.0).abs() < 1e-9);
+        assert_eq!(etok(0.0), 0.0);
+        assert!((etok(-100.0) + etok(100.0)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn angles() {
+        assert!((deg_to_rad(180.0) - std::f64::consts::PI).abs() < 1e-15);
+        assert!((rad_to_deg(deg_to_rad(37.5)) - 37.5).abs() < 1e-12);
+        assert_eq!(mrad_to_rad(3.0), 0.003);
+        assert_eq!(rad_to_mrad(0.0075), 7.5);
+    }
+}